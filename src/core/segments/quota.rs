@@ -1,14 +1,309 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::fs;
+use std::fs::{self, File};
+use std::net::UdpSocket;
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const MIN_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Arg that tells a freshly-exec'd copy of this binary to become the background
+/// quota daemon instead of rendering a statusline. `main()` must check
+/// `QuotaSegment::try_run_daemon()` before anything else: `ccline` is spawned fresh
+/// on every prompt render (read stdin once, `collect()`, print, exit), so a thread
+/// spawned inside that process is killed the instant it exits — almost always before
+/// an in-flight HTTP fetch completes. The daemon has to be a separate, detached OS
+/// process that outlives any single render.
+const DAEMON_ARG: &str = "--__quota-daemon";
 
 #[derive(Default)]
 pub struct QuotaSegment;
 
+/// A pending (re)fetch for one provider, ordered by its next-run time.
+struct RefreshJob {
+    provider: ProviderConfig,
+    cache_ttl: u64,
+    timeout: u64,
+    backoff: Duration,
+}
+
+/// The persistent background process that keeps the quota cache warm so `collect`
+/// never blocks on HTTP. Runs as a detached OS process (spawned via `DAEMON_ARG`),
+/// not a thread, since it must outlive any single statusline render.
+///
+/// Owns a time-ordered queue of `RefreshJob`s: the main loop always runs the job with
+/// the earliest `next_run`, then re-inserts it at `now + cache_ttl` on success or
+/// `now + backoff` (doubled, capped at `MAX_BACKOFF`) on failure. Render processes
+/// can't reach into this queue directly (they're a different OS process), so a stale
+/// cache is signalled via a marker file that the loop polls for instead.
+struct Daemon;
+
+impl Daemon {
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Runs forever as the daemon's main loop. Never returns — except by exiting
+    /// immediately if it lost the race to become the daemon (see `acquire_daemon_lock`).
+    fn run_forever() -> ! {
+        let Some(_lock) = QuotaSegment::acquire_daemon_lock() else {
+            // Another process already holds the lock and is acting as the daemon.
+            // `ensure_daemon_running` can't fully prevent two of these from being
+            // spawned concurrently, so the loser just steps aside here instead of
+            // polling and fetching alongside the winner.
+            std::process::exit(0);
+        };
+
+        let mut queue: BTreeMap<Instant, RefreshJob> = BTreeMap::new();
+        let mut gossip_listening = false;
+
+        loop {
+            // Reloaded every iteration, not just once at startup: editing
+            // `quota.toml` (adding/removing a provider, changing `cache_ttl`) while
+            // this daemon is already running needs to take effect without anyone
+            // having to find and kill the old process.
+            let config = QuotaSegment::load_quota_config();
+            Self::sync_jobs_with_config(&mut queue, &config);
+
+            if !gossip_listening {
+                if let Some(bind_addr) = &config.gossip_bind {
+                    if let Ok(socket) = UdpSocket::bind(bind_addr) {
+                        thread::spawn(move || Gossip::global().listen(socket));
+                        gossip_listening = true;
+                    }
+                }
+            }
+
+            Self::merge_refresh_requests(&mut queue);
+
+            let now = Instant::now();
+            match queue.keys().next().copied() {
+                Some(next_run) if next_run <= now => {
+                    let job = queue.remove(&next_run).unwrap();
+                    let ok = QuotaSegment::refresh_and_cache(&job.provider, job.timeout, &config)
+                        .is_some();
+                    let (next_run, next_backoff) = if ok {
+                        (Instant::now() + Duration::from_secs(job.cache_ttl), MIN_BACKOFF)
+                    } else {
+                        (Instant::now() + job.backoff, (job.backoff * 2).min(MAX_BACKOFF))
+                    };
+                    queue.insert(
+                        next_run,
+                        RefreshJob {
+                            provider: job.provider,
+                            cache_ttl: job.cache_ttl,
+                            timeout: job.timeout,
+                            backoff: next_backoff,
+                        },
+                    );
+                }
+                Some(next_run) => thread::sleep((next_run - now).min(Self::POLL_INTERVAL)),
+                None => thread::sleep(Self::POLL_INTERVAL),
+            }
+        }
+    }
+
+    /// Reconciles the job queue against the just-reloaded config: providers removed
+    /// from `quota.toml` lose their job, newly-added ones get scheduled immediately,
+    /// and ones already queued pick up their (possibly edited) settings in place
+    /// without disturbing their `next_run`/backoff.
+    fn sync_jobs_with_config(queue: &mut BTreeMap<Instant, RefreshJob>, config: &QuotaConfig) {
+        let known_ids: Vec<&str> = config.providers.iter().map(|p| p.id.as_str()).collect();
+        let stale: Vec<Instant> = queue
+            .iter()
+            .filter(|(_, job)| !known_ids.contains(&job.provider.id.as_str()))
+            .map(|(&when, _)| when)
+            .collect();
+        for when in stale {
+            queue.remove(&when);
+        }
+
+        for provider in &config.providers {
+            if let Some((&when, _)) = queue.iter().find(|(_, job)| job.provider.id == provider.id) {
+                let mut job = queue.remove(&when).unwrap();
+                job.provider = provider.clone();
+                job.cache_ttl = config.cache_ttl;
+                job.timeout = config.timeout;
+                queue.insert(when, job);
+            } else {
+                queue.insert(
+                    Instant::now(),
+                    RefreshJob {
+                        provider: provider.clone(),
+                        cache_ttl: config.cache_ttl,
+                        timeout: config.timeout,
+                        backoff: MIN_BACKOFF,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Picks up refresh-request marker files left by render processes (see
+    /// `QuotaSegment::request_refresh_asap`) and advances the matching job's
+    /// `next_run` to now, merging rather than duplicating it.
+    fn merge_refresh_requests(queue: &mut BTreeMap<Instant, RefreshJob>) {
+        for provider_id in Self::pending_refresh_requests() {
+            if let Some((&when, _)) = queue.iter().find(|(_, job)| job.provider.id == provider_id) {
+                if when > Instant::now() {
+                    let job = queue.remove(&when).unwrap();
+                    queue.insert(Instant::now(), job);
+                }
+            }
+        }
+    }
+
+    fn pending_refresh_requests() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(QuotaSegment::get_config_dir()) else {
+            return Vec::new();
+        };
+        let mut provider_ids = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(id) = name
+                .strip_prefix("quota_refresh_")
+                .and_then(|s| s.strip_suffix(".request"))
+            else {
+                continue;
+            };
+            provider_ids.push(id.to_string());
+            let _ = fs::remove_file(&path);
+        }
+        provider_ids
+    }
+}
+
+/// Compact UDP broadcast describing one provider's consumption as seen by one node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GossipMessage {
+    provider_id: String,
+    total_cost_usd: f64,
+    request_count: u64,
+    fetched_at: u64,
+    node_id: String,
+}
+
+/// The last gossip message received from one peer for one provider.
+struct PeerEntry {
+    total_cost_usd: f64,
+    request_count: u64,
+    received_at: Instant,
+}
+
+/// Background UDP listener that folds peers' `GossipMessage`s into a local map, so a
+/// shared team/daily budget reflects the whole fleet's spend, not just this node's.
+struct Gossip {
+    peers: Mutex<HashMap<String, PeerEntry>>,
+}
+
+impl Gossip {
+    fn global() -> &'static Gossip {
+        static INSTANCE: OnceLock<Gossip> = OnceLock::new();
+        INSTANCE.get_or_init(|| Gossip {
+            peers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Stable identifier for this node, shared by every `ccline`/daemon invocation on
+    /// this machine. Generated once and persisted to disk, since each render is a
+    /// fresh process and a per-process id would defeat dedup of this node's peer
+    /// entries on every other node's gossip map.
+    fn node_id() -> String {
+        let path = QuotaSegment::get_node_id_path();
+        if let Ok(existing) = fs::read_to_string(&path) {
+            let existing = existing.trim();
+            if !existing.is_empty() {
+                return existing.to_string();
+            }
+        }
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let id = format!("{:x}", nanos);
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(&path, &id);
+        id
+    }
+
+    fn listen(&self, socket: UdpSocket) {
+        let mut buf = [0u8; 1024];
+        loop {
+            let Ok((len, _src)) = socket.recv_from(&mut buf) else {
+                continue;
+            };
+            let Ok(message) = serde_json::from_slice::<GossipMessage>(&buf[..len]) else {
+                continue;
+            };
+            if message.node_id == Self::node_id() {
+                continue;
+            }
+            let mut peers = self.peers.lock().unwrap();
+            peers.insert(
+                format!("{}:{}", message.node_id, message.provider_id),
+                PeerEntry {
+                    total_cost_usd: message.total_cost_usd,
+                    request_count: message.request_count,
+                    received_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Sends this node's latest numbers for `provider_id` to every configured peer.
+    fn broadcast(provider_id: &str, data: &QuotaData, peers: &[String]) {
+        if peers.is_empty() {
+            return;
+        }
+        let message = GossipMessage {
+            provider_id: provider_id.to_string(),
+            total_cost_usd: data.total_cost_usd,
+            request_count: data.request_count,
+            fetched_at: QuotaSegment::current_timestamp(),
+            node_id: Self::node_id(),
+        };
+        let Ok(payload) = serde_json::to_vec(&message) else {
+            return;
+        };
+        let Ok(socket) = UdpSocket::bind("0.0.0.0:0") else {
+            return;
+        };
+        for peer in peers {
+            let _ = socket.send_to(&payload, peer);
+        }
+    }
+
+    /// Prunes every peer entry older than `ttl` out of the map, then sums the
+    /// `total_cost_usd`/`request_count` of what's left for `provider_id`. Received
+    /// timestamps are advisory only (clock skew tolerant) — freshness is judged by
+    /// local monotonic receive time, not the peer's clock.
+    fn summed_totals(&self, provider_id: &str, ttl: Duration) -> (f64, u64) {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        peers.retain(|_, entry| now.duration_since(entry.received_at) < ttl);
+
+        let suffix = format!(":{}", provider_id);
+        peers
+            .iter()
+            .filter(|(key, _)| key.ends_with(&suffix))
+            .fold((0.0, 0u64), |(cost, count), (_, entry)| {
+                (cost + entry.total_cost_usd, count + entry.request_count)
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct QuotaCache {
     fetched_at: u64,
@@ -25,31 +320,39 @@ struct QuotaData {
     api_healthy: bool,
 }
 
-// API response structure for relay.nf.video/v1/usage
-#[derive(Debug, Deserialize)]
-struct ApiResponse {
-    usage: UsageData,
-    limits: LimitsData,
+/// One quota provider/relay declared under `[[providers]]` in `quota.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProviderConfig {
+    id: String,
+    api_url: String,
+    /// Defaults to the shared `quota_token` file when not set.
+    #[serde(default)]
+    token_path: Option<String>,
+    #[serde(default)]
+    response_schema: ResponseSchema,
 }
 
-#[derive(Debug, Deserialize)]
-struct UsageData {
-    #[serde(rename = "remainingUSD")]
-    remaining_usd: f64,
-    #[serde(rename = "dailyLimitUSD")]
-    daily_limit_usd: f64,
-    #[serde(rename = "totalCostUSD")]
-    total_cost_usd: f64,
-    #[serde(rename = "requestCount")]
-    request_count: u64,
-    #[serde(rename = "canMakeRequest")]
-    can_make_request: bool,
+/// Maps dotted JSON field paths in a provider's response onto the `QuotaData` fields.
+/// Defaults match the relay.nf.video `/v1/usage` shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResponseSchema {
+    remaining_usd: String,
+    daily_limit_usd: String,
+    total_cost_usd: String,
+    request_count: String,
+    can_make_request: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct LimitsData {
-    #[serde(rename = "dailyUSD")]
-    daily_usd: f64,
+impl Default for ResponseSchema {
+    fn default() -> Self {
+        Self {
+            remaining_usd: "usage.remainingUSD".to_string(),
+            daily_limit_usd: "limits.dailyUSD".to_string(),
+            total_cost_usd: "usage.totalCostUSD".to_string(),
+            request_count: "usage.requestCount".to_string(),
+            can_make_request: "usage.canMakeRequest".to_string(),
+        }
+    }
 }
 
 impl QuotaSegment {
@@ -64,8 +367,8 @@ impl QuotaSegment {
             .join("ccline")
     }
 
-    fn get_cache_path() -> PathBuf {
-        Self::get_config_dir().join("quota_cache.json")
+    fn get_cache_path(provider_id: &str) -> PathBuf {
+        Self::get_config_dir().join(format!("quota_cache_{}.json", provider_id))
     }
 
     fn get_token_path() -> PathBuf {
@@ -76,18 +379,145 @@ impl QuotaSegment {
         Self::get_config_dir().join("quota.toml")
     }
 
+    fn get_daemon_pidfile_path() -> PathBuf {
+        Self::get_config_dir().join("quota_daemon.pid")
+    }
+
+    fn get_refresh_request_path(provider_id: &str) -> PathBuf {
+        Self::get_config_dir().join(format!("quota_refresh_{}.request", provider_id))
+    }
+
+    fn get_node_id_path() -> PathBuf {
+        Self::get_config_dir().join("quota_node_id")
+    }
+
+    /// Entry point for `main()` to check before doing anything else. If this process
+    /// was exec'd with `DAEMON_ARG`, it becomes the background daemon and never
+    /// returns; otherwise it's a no-op and normal statusline rendering proceeds.
+    pub fn try_run_daemon() {
+        if Self::is_daemon_invocation() {
+            Daemon::run_forever();
+        }
+    }
+
+    fn is_daemon_invocation() -> bool {
+        std::env::args().any(|arg| arg == DAEMON_ARG)
+    }
+
+    /// Opens the pidfile and takes a non-blocking exclusive `flock` on it. A bare PID
+    /// in a text file can't be trusted — if the daemon dies, nothing clears the file,
+    /// and the PID can get reused by an unrelated process, wedging quota refresh
+    /// forever. The OS releases `flock`s automatically when the holding process
+    /// exits or crashes, so "can I lock it" is a liveness check that self-heals; the
+    /// returned `File` must be kept alive for as long as the caller wants to hold
+    /// the lock (dropping it releases the lock).
+    fn acquire_daemon_lock() -> Option<File> {
+        let dir = Self::get_config_dir();
+        let _ = fs::create_dir_all(&dir);
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(Self::get_daemon_pidfile_path())
+            .ok()?;
+        file.try_lock_exclusive().ok()?;
+        let _ = file.set_len(0);
+        use std::io::Write;
+        let _ = (&file).write_all(std::process::id().to_string().as_bytes());
+        Some(file)
+    }
+
+    /// Best-effort liveness check: true if some process currently holds the daemon
+    /// lock. Takes the lock itself only for the duration of this call instead of
+    /// trusting a pidfile's PID. A daemon whose `acquire_daemon_lock` lands in that
+    /// same instant fails to lock and exits; the next render's `ensure_daemon_running`
+    /// then spawns another.
+    fn daemon_is_running() -> bool {
+        let dir = Self::get_config_dir();
+        let _ = fs::create_dir_all(&dir);
+        let Ok(file) = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(Self::get_daemon_pidfile_path())
+        else {
+            return false;
+        };
+        match file.try_lock_exclusive() {
+            Ok(()) => {
+                let _ = file.unlock();
+                false
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Spawns a detached copy of this binary running as the quota daemon, unless one
+    /// is already alive per `daemon_is_running`. Safe to call on every render: it's a
+    /// no-op once the daemon is up. Two renders can still race past this check and
+    /// both spawn a copy — the loser notices it lost the `flock` at the top of
+    /// `Daemon::run_forever` and exits immediately, so at most one ever does work.
+    fn ensure_daemon_running() {
+        if Self::daemon_is_running() {
+            return;
+        }
+        let Ok(exe) = std::env::current_exe() else {
+            return;
+        };
+        let _ = Command::new(exe)
+            .arg(DAEMON_ARG)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+
+    /// Leaves a marker file asking the daemon to refresh `provider_id` as soon as it
+    /// next polls, without duplicating any pending request for the same provider.
+    fn request_refresh_asap(provider_id: &str) {
+        let dir = Self::get_config_dir();
+        let _ = fs::create_dir_all(&dir);
+        let _ = fs::write(Self::get_refresh_request_path(provider_id), b"");
+    }
+
     fn load_quota_config() -> QuotaConfig {
         let config_path = Self::get_config_path();
         if config_path.exists() {
             if let Ok(content) = fs::read_to_string(&config_path) {
-                if let Ok(config) = toml::from_str(&content) {
-                    return config;
+                if let Ok(mut value) = content.parse::<toml::Value>() {
+                    Self::migrate_legacy_api_url(&mut value);
+                    if let Ok(config) = QuotaConfig::deserialize(value) {
+                        return config;
+                    }
                 }
             }
         }
         QuotaConfig::default()
     }
 
+    /// Folds a pre-multi-provider `api_url = "..."` key into a single-entry
+    /// `providers` list, so a `quota.toml` written before the `providers` array
+    /// existed keeps pointing at the relay it was configured for instead of
+    /// silently falling back to the hardcoded default. No-op once `providers` is
+    /// present, since that always takes priority over the legacy key.
+    fn migrate_legacy_api_url(value: &mut toml::Value) {
+        let Some(table) = value.as_table_mut() else {
+            return;
+        };
+        if table.contains_key("providers") {
+            return;
+        }
+        let Some(api_url) = table.remove("api_url") else {
+            return;
+        };
+
+        let mut provider = toml::map::Map::new();
+        provider.insert("id".to_string(), toml::Value::String("relay".to_string()));
+        provider.insert("api_url".to_string(), api_url);
+        table.insert(
+            "providers".to_string(),
+            toml::Value::Array(vec![toml::Value::Table(provider)]),
+        );
+    }
+
     fn current_timestamp() -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -95,8 +525,8 @@ impl QuotaSegment {
             .unwrap_or(0)
     }
 
-    fn load_cache() -> Option<QuotaCache> {
-        let cache_path = Self::get_cache_path();
+    fn load_cache(provider_id: &str) -> Option<QuotaCache> {
+        let cache_path = Self::get_cache_path(provider_id);
         if !cache_path.exists() {
             return None;
         }
@@ -104,8 +534,8 @@ impl QuotaSegment {
         serde_json::from_str(&content).ok()
     }
 
-    fn save_cache(cache: &QuotaCache) {
-        let cache_path = Self::get_cache_path();
+    fn save_cache(provider_id: &str, cache: &QuotaCache) {
+        let cache_path = Self::get_cache_path(provider_id);
         if let Ok(content) = serde_json::to_string_pretty(cache) {
             let _ = fs::write(&cache_path, content);
         }
@@ -119,53 +549,202 @@ impl QuotaSegment {
             .filter(|s| !s.is_empty())
     }
 
-    fn fetch_quota(config: &QuotaConfig, token: &str) -> Option<QuotaData> {
+    fn load_provider_token(provider: &ProviderConfig) -> Option<String> {
+        match &provider.token_path {
+            Some(path) => fs::read_to_string(Self::expand_tilde(path))
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            None => Self::load_token(),
+        }
+    }
+
+    /// Looks up a dotted path (e.g. `"usage.remainingUSD"`) in a JSON response body.
+    fn json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+        path.split('.').try_fold(value, |v, key| v.get(key))
+    }
+
+    fn fetch_provider_quota(provider: &ProviderConfig, timeout: u64) -> Option<QuotaData> {
+        let token = Self::load_provider_token(provider)?;
         let auth_header = format!("Bearer {}", token);
 
-        let response = ureq::get(&config.api_url)
+        let response = ureq::get(&provider.api_url)
             .set("Authorization", &auth_header)
             .set("Accept", "application/json")
-            .timeout(std::time::Duration::from_secs(config.timeout))
+            .timeout(Duration::from_secs(timeout))
             .call()
             .ok()?;
 
-        let api_response: ApiResponse = response.into_json().ok()?;
+        let body: serde_json::Value = response.into_json().ok()?;
+        let schema = &provider.response_schema;
 
         Some(QuotaData {
-            remaining_usd: api_response.usage.remaining_usd,
-            daily_limit_usd: api_response.usage.daily_limit_usd,
-            total_cost_usd: api_response.usage.total_cost_usd,
-            request_count: api_response.usage.request_count,
-            can_make_request: api_response.usage.can_make_request,
+            remaining_usd: Self::json_path(&body, &schema.remaining_usd)?.as_f64()?,
+            daily_limit_usd: Self::json_path(&body, &schema.daily_limit_usd)?.as_f64()?,
+            total_cost_usd: Self::json_path(&body, &schema.total_cost_usd)?.as_f64()?,
+            request_count: Self::json_path(&body, &schema.request_count)?.as_u64()?,
+            can_make_request: Self::json_path(&body, &schema.can_make_request)?.as_bool()?,
             api_healthy: true,
         })
     }
 
-    fn get_quota_data(config: &QuotaConfig) -> Option<QuotaData> {
-        let now = Self::current_timestamp();
+    /// Fetches fresh quota data for one provider and persists it to that provider's
+    /// cache file. Only ever called from inside `Daemon::run_forever`; never called
+    /// on the rendering path. Takes `config` rather than reloading it, since the
+    /// caller already reloads it once per loop iteration.
+    fn refresh_and_cache(provider: &ProviderConfig, timeout: u64, config: &QuotaConfig) -> Option<QuotaData> {
+        let data = Self::fetch_provider_quota(provider, timeout)?;
+        Self::save_cache(
+            &provider.id,
+            &QuotaCache {
+                fetched_at: Self::current_timestamp(),
+                data: data.clone(),
+            },
+        );
 
-        // Check cache first
-        if let Some(cache) = Self::load_cache() {
-            if now - cache.fetched_at < config.cache_ttl {
-                return Some(cache.data);
-            }
+        Gossip::broadcast(&provider.id, &data, &config.gossip_peers);
+
+        if let Some(metrics_path) = &config.metrics_path {
+            let snapshot = Self::load_provider_snapshot(config);
+            let aggregated = Self::aggregate(snapshot.values());
+            Self::write_prometheus_metrics(metrics_path, &aggregated);
         }
 
-        // Load token
-        let token = Self::load_token()?;
+        Some(data)
+    }
 
-        // Fetch new data
-        if let Some(data) = Self::fetch_quota(config, &token) {
-            let cache = QuotaCache {
-                fetched_at: now,
-                data: data.clone(),
-            };
-            Self::save_cache(&cache);
-            return Some(data);
+    fn expand_tilde(path: &str) -> PathBuf {
+        match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(rest),
+            None => PathBuf::from(path),
         }
+    }
+
+    /// Builds the Prometheus text exposition body for a quota snapshot. Pure and
+    /// side-effect free so it can be unit tested without touching the filesystem.
+    fn render_prometheus_body(data: &QuotaData) -> String {
+        format!(
+            "# HELP ccline_quota_remaining_usd Remaining USD quota for the current period.\n\
+             # TYPE ccline_quota_remaining_usd gauge\n\
+             ccline_quota_remaining_usd {remaining}\n\
+             # HELP ccline_quota_daily_limit_usd Configured daily USD quota limit.\n\
+             # TYPE ccline_quota_daily_limit_usd gauge\n\
+             ccline_quota_daily_limit_usd {limit}\n\
+             # HELP ccline_quota_total_cost_usd Total USD cost accrued so far today.\n\
+             # TYPE ccline_quota_total_cost_usd counter\n\
+             ccline_quota_total_cost_usd {total}\n\
+             # HELP ccline_quota_request_count Total number of requests counted against quota.\n\
+             # TYPE ccline_quota_request_count counter\n\
+             ccline_quota_request_count {requests}\n\
+             # HELP ccline_quota_can_make_request Whether another request can currently be made.\n\
+             # TYPE ccline_quota_can_make_request gauge\n\
+             ccline_quota_can_make_request {can_make_request}\n\
+             # HELP ccline_quota_api_healthy Health flag of the cached quota data; set on each successful fetch, a failed fetch leaves it unchanged.\n\
+             # TYPE ccline_quota_api_healthy gauge\n\
+             ccline_quota_api_healthy {api_healthy}\n",
+            remaining = data.remaining_usd,
+            limit = data.daily_limit_usd,
+            total = data.total_cost_usd,
+            requests = data.request_count,
+            can_make_request = data.can_make_request as u8,
+            api_healthy = data.api_healthy as u8,
+        )
+    }
+
+    /// Writes the current quota snapshot to `metrics_path` in Prometheus text
+    /// exposition format, so the node_exporter textfile collector can scrape it.
+    /// Writes to a `.tmp` sibling and renames over the target so a concurrent
+    /// scrape (textfile collectors poll on their own timer, and this now runs on
+    /// every render as well as every daemon fetch) never sees a truncated file.
+    fn write_prometheus_metrics(metrics_path: &str, data: &QuotaData) {
+        let path = Self::expand_tilde(metrics_path);
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        let _ = fs::create_dir_all(parent);
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("quota.prom");
+        let tmp_path = parent.join(format!("{}.tmp", file_name));
 
-        // Fallback to stale cache if fetch failed
-        Self::load_cache().map(|c| c.data)
+        let body = Self::render_prometheus_body(data);
+        if fs::write(&tmp_path, body).is_ok() {
+            let _ = fs::rename(&tmp_path, &path);
+        }
+    }
+
+    /// Reads whatever is cached for each provider, without blocking, and folds in
+    /// gossiped peer consumption when configured. Returns the provider id mapped to
+    /// its last-fetched `QuotaData`; providers with no cache yet are simply absent.
+    fn load_provider_snapshot(config: &QuotaConfig) -> HashMap<String, QuotaData> {
+        // The gossip listener only ever runs inside `Daemon::run_forever` (started when
+        // `gossip_bind` is set) — a render process is too short-lived to host it, so this
+        // just reads whatever the daemon has already folded into `Gossip::global()`.
+        let gossip_active = config.gossip_bind.is_some() || !config.gossip_peers.is_empty();
+        let ttl = Duration::from_secs(config.gossip_peer_ttl);
+
+        config
+            .providers
+            .iter()
+            .filter_map(|p| Self::load_cache(&p.id).map(|c| (p.id.clone(), c.data)))
+            .map(|(id, mut data)| {
+                if gossip_active {
+                    let (peer_cost, _peer_requests) = Gossip::global().summed_totals(&id, ttl);
+                    let effective_cost = data.total_cost_usd.max(peer_cost);
+                    data.remaining_usd = (data.daily_limit_usd - effective_cost).max(0.0);
+                }
+                (id, data)
+            })
+            .collect()
+    }
+
+    /// Sums the USD and request-count fields across providers and ANDs the
+    /// health/can-make-request flags, so one unhealthy relay drags the whole
+    /// aggregate unhealthy.
+    fn aggregate<'a>(entries: impl Iterator<Item = &'a QuotaData>) -> QuotaData {
+        entries.fold(
+            QuotaData {
+                can_make_request: true,
+                api_healthy: true,
+                ..QuotaData::default()
+            },
+            |mut acc, d| {
+                acc.remaining_usd += d.remaining_usd;
+                acc.daily_limit_usd += d.daily_limit_usd;
+                acc.total_cost_usd += d.total_cost_usd;
+                acc.request_count += d.request_count;
+                acc.can_make_request &= d.can_make_request;
+                acc.api_healthy &= d.api_healthy;
+                acc
+            },
+        )
+    }
+
+    /// Reads whatever is in the cache and never blocks. If any provider's cache is
+    /// missing or stale, nudges the background daemon to refresh it sooner but still
+    /// returns the (possibly stale) aggregate immediately so the statusline stays
+    /// instantaneous. Returns the aggregate plus the per-provider breakdown.
+    fn get_quota_data(config: &QuotaConfig) -> Option<(QuotaData, HashMap<String, QuotaData>)> {
+        Self::ensure_daemon_running();
+
+        let now = Self::current_timestamp();
+        for provider in &config.providers {
+            let is_stale = Self::load_cache(&provider.id)
+                .map(|c| now - c.fetched_at >= config.cache_ttl)
+                .unwrap_or(true);
+            if is_stale {
+                Self::request_refresh_asap(&provider.id);
+            }
+        }
+
+        let per_provider = Self::load_provider_snapshot(config);
+        if per_provider.is_empty() {
+            return None;
+        }
+
+        let aggregated = Self::aggregate(per_provider.values());
+        Some((aggregated, per_provider))
     }
 
     fn format_usd(n: f64) -> String {
@@ -233,21 +812,53 @@ impl QuotaSegment {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct QuotaConfig {
-    api_url: String,
+    #[serde(default = "default_providers")]
+    providers: Vec<ProviderConfig>,
     cache_ttl: u64,
     timeout: u64,
     show_requests: bool,
     warning_threshold: f64,
+    /// When set, every successful fetch also writes a Prometheus textfile-collector
+    /// exposition file here (e.g. `~/.claude/ccline/quota.prom`).
+    metrics_path: Option<String>,
+    /// Address to listen on for peer gossip (e.g. `"0.0.0.0:49111"`). Enables folding
+    /// other machines' consumption into the displayed remaining quota.
+    #[serde(default)]
+    gossip_bind: Option<String>,
+    /// Peer addresses (`host:port`) to broadcast this node's consumption to after
+    /// every successful fetch.
+    #[serde(default)]
+    gossip_peers: Vec<String>,
+    /// How long a peer's gossiped numbers stay valid before being dropped as stale.
+    #[serde(default = "default_gossip_peer_ttl")]
+    gossip_peer_ttl: u64,
+}
+
+fn default_gossip_peer_ttl() -> u64 {
+    120
+}
+
+fn default_providers() -> Vec<ProviderConfig> {
+    vec![ProviderConfig {
+        id: "relay".to_string(),
+        api_url: "https://relay.nf.video/v1/usage".to_string(),
+        token_path: None,
+        response_schema: ResponseSchema::default(),
+    }]
 }
 
 impl Default for QuotaConfig {
     fn default() -> Self {
         Self {
-            api_url: "https://relay.nf.video/v1/usage".to_string(),
+            providers: default_providers(),
             cache_ttl: 30,
             timeout: 5,
             show_requests: false,
             warning_threshold: 0.15,
+            metrics_path: None,
+            gossip_bind: None,
+            gossip_peers: Vec::new(),
+            gossip_peer_ttl: default_gossip_peer_ttl(),
         }
     }
 }
@@ -255,7 +866,14 @@ impl Default for QuotaConfig {
 impl Segment for QuotaSegment {
     fn collect(&self, _input: &InputData) -> Option<SegmentData> {
         let config = Self::load_quota_config();
-        let data = Self::get_quota_data(&config)?;
+        let (data, per_provider) = Self::get_quota_data(&config)?;
+
+        // Independent of the daemon's post-fetch write in `refresh_and_cache`, so the
+        // textfile export reflects the latest cache even on renders the daemon never
+        // touches (e.g. before its first refresh cycle completes).
+        if let Some(metrics_path) = &config.metrics_path {
+            QuotaSegment::write_prometheus_metrics(metrics_path, &data);
+        }
 
         let remaining_pct = data.remaining_usd / data.daily_limit_usd;
 
@@ -299,6 +917,33 @@ impl Segment for QuotaSegment {
         metadata.insert("can_make_request".to_string(), data.can_make_request.to_string());
         metadata.insert("api_healthy".to_string(), data.api_healthy.to_string());
 
+        for (provider_id, provider_data) in &per_provider {
+            metadata.insert(
+                format!("{}.remaining_usd", provider_id),
+                provider_data.remaining_usd.to_string(),
+            );
+            metadata.insert(
+                format!("{}.daily_limit_usd", provider_id),
+                provider_data.daily_limit_usd.to_string(),
+            );
+            metadata.insert(
+                format!("{}.total_cost_usd", provider_id),
+                provider_data.total_cost_usd.to_string(),
+            );
+            metadata.insert(
+                format!("{}.request_count", provider_id),
+                provider_data.request_count.to_string(),
+            );
+            metadata.insert(
+                format!("{}.can_make_request", provider_id),
+                provider_data.can_make_request.to_string(),
+            );
+            metadata.insert(
+                format!("{}.api_healthy", provider_id),
+                provider_data.api_healthy.to_string(),
+            );
+        }
+
         Some(SegmentData {
             primary,
             secondary,
@@ -310,3 +955,117 @@ impl Segment for QuotaSegment {
         SegmentId::Quota
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrate_legacy_api_url_folds_into_providers() {
+        let mut value: toml::Value = r#"
+            api_url = "https://my-relay.example/v1/usage"
+            cache_ttl = 60
+            timeout = 5
+            show_requests = false
+            warning_threshold = 0.1
+            "#
+        .parse()
+        .unwrap();
+
+        QuotaSegment::migrate_legacy_api_url(&mut value);
+        let config = QuotaConfig::deserialize(value).unwrap();
+
+        assert_eq!(config.providers.len(), 1);
+        assert_eq!(config.providers[0].api_url, "https://my-relay.example/v1/usage");
+    }
+
+    #[test]
+    fn migrate_legacy_api_url_leaves_providers_list_alone() {
+        let mut value: toml::Value = r#"
+            cache_ttl = 60
+            timeout = 5
+            show_requests = false
+            warning_threshold = 0.1
+
+            [[providers]]
+            id = "relay"
+            api_url = "https://configured-relay.example/v1/usage"
+            "#
+        .parse()
+        .unwrap();
+
+        QuotaSegment::migrate_legacy_api_url(&mut value);
+        let config = QuotaConfig::deserialize(value).unwrap();
+
+        assert_eq!(config.providers.len(), 1);
+        assert_eq!(config.providers[0].api_url, "https://configured-relay.example/v1/usage");
+    }
+
+    #[test]
+    fn json_path_finds_nested_value() {
+        let body: serde_json::Value = serde_json::json!({
+            "usage": { "remainingUSD": 12.5 }
+        });
+        assert_eq!(
+            QuotaSegment::json_path(&body, "usage.remainingUSD"),
+            Some(&serde_json::json!(12.5))
+        );
+    }
+
+    #[test]
+    fn json_path_returns_none_instead_of_panicking_on_miss() {
+        let body: serde_json::Value = serde_json::json!({ "usage": {} });
+        assert_eq!(QuotaSegment::json_path(&body, "usage.remainingUSD"), None);
+        assert_eq!(QuotaSegment::json_path(&body, "missing.nested.path"), None);
+    }
+
+    #[test]
+    fn aggregate_sums_usd_and_ands_health_flags() {
+        let healthy = QuotaData {
+            remaining_usd: 10.0,
+            daily_limit_usd: 20.0,
+            total_cost_usd: 10.0,
+            request_count: 5,
+            can_make_request: true,
+            api_healthy: true,
+        };
+        let unhealthy = QuotaData {
+            remaining_usd: 0.0,
+            daily_limit_usd: 5.0,
+            total_cost_usd: 5.0,
+            request_count: 2,
+            can_make_request: false,
+            api_healthy: false,
+        };
+
+        let aggregated = QuotaSegment::aggregate([&healthy, &unhealthy].into_iter());
+
+        assert_eq!(aggregated.remaining_usd, 10.0);
+        assert_eq!(aggregated.daily_limit_usd, 25.0);
+        assert_eq!(aggregated.total_cost_usd, 15.0);
+        assert_eq!(aggregated.request_count, 7);
+        assert!(!aggregated.can_make_request);
+        assert!(!aggregated.api_healthy);
+    }
+
+    #[test]
+    fn render_prometheus_body_includes_all_fields() {
+        let data = QuotaData {
+            remaining_usd: 12.5,
+            daily_limit_usd: 20.0,
+            total_cost_usd: 7.5,
+            request_count: 3,
+            can_make_request: true,
+            api_healthy: false,
+        };
+
+        let body = QuotaSegment::render_prometheus_body(&data);
+
+        assert!(body.contains("ccline_quota_remaining_usd 12.5"));
+        assert!(body.contains("ccline_quota_daily_limit_usd 20"));
+        assert!(body.contains("ccline_quota_total_cost_usd 7.5"));
+        assert!(body.contains("ccline_quota_request_count 3"));
+        assert!(body.contains("ccline_quota_can_make_request 1"));
+        assert!(body.contains("ccline_quota_api_healthy 0"));
+    }
+}